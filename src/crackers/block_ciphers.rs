@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+/// The block cipher mode detected for an oracle's output.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Mode {
+    Ecb,
+    Cbc,
+}
+
+/// Returns the number of duplicate blocks of `block_size` bytes found in a ciphertext. Under ECB,
+/// identical plaintext blocks always encrypt to identical ciphertext blocks, so a high count is a
+/// strong signal that a ciphertext was encrypted under ECB.
+pub fn count_duplicate_blocks(ciphertext: &[u8], block_size: usize) -> usize {
+    let blocks: Vec<&[u8]> = ciphertext.chunks(block_size).collect();
+    let mut seen_blocks = HashSet::new();
+
+    blocks
+        .iter()
+        .filter(|block| !seen_blocks.insert(*block))
+        .count()
+}
+
+/// Returns the index of the ciphertext among `candidates` that is most likely to have been
+/// encrypted under ECB, i.e. the one with the most duplicate 16-byte blocks.
+pub fn detect_ecb_encrypted(candidates: &[&[u8]]) -> usize {
+    const ECB_BLOCK_SIZE: usize = 16;
+
+    candidates
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, ciphertext)| count_duplicate_blocks(ciphertext, ECB_BLOCK_SIZE))
+        .map(|(index, _)| index)
+        .expect("we know a maximum will be found")
+}
+
+/// Detects whether a black-box encryption oracle is operating in ECB or CBC mode, purely by
+/// observing its output. Feeds the oracle a plaintext of at least three identical blocks: under
+/// ECB, identical plaintext blocks always produce identical ciphertext blocks, so at least two of
+/// the returned blocks will collide; under CBC they won't, since each block is chained off the
+/// previous ciphertext block.
+pub fn detect_ecb_or_cbc<F: Fn(&[u8]) -> Vec<u8>>(oracle: F, block_size: usize) -> Mode {
+    let plaintext = vec![b'A'; block_size * 3];
+    let ciphertext = oracle(&plaintext);
+
+    if count_duplicate_blocks(&ciphertext, block_size) > 0 {
+        Mode::Ecb
+    } else {
+        Mode::Cbc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::{BufRead, BufReader};
+
+    use crate::crackers::block_ciphers::*;
+
+    // Solution to Cryptopals set 01 challenge 08.
+    #[test]
+    fn can_detect_ecb_encrypted_ciphertext() {
+        let filename = "./src/crackers/8.txt";
+        let file = File::open(filename).expect("could not open file");
+        let ciphertexts_bytes_vec = BufReader::new(file)
+            .lines()
+            .map(|x| hex::decode(x.expect("could not read line"))
+                .expect("could not convert hex to bytes"))
+            .collect::<Vec<Vec<u8>>>();
+        let ciphertexts_bytes = ciphertexts_bytes_vec.iter().map(|x| &x[..]).collect::<Vec<&[u8]>>();
+
+        let ecb_index = detect_ecb_encrypted(&ciphertexts_bytes);
+
+        assert_eq!(ecb_index, 132);
+    }
+
+    // Solution to Cryptopals set 02 challenge 11.
+    #[test]
+    fn can_detect_ecb_oracle() {
+        let ecb_oracle = |plaintext: &[u8]| plaintext.to_vec();
+        assert_eq!(detect_ecb_or_cbc(ecb_oracle, 16), Mode::Ecb);
+    }
+
+    #[test]
+    fn can_detect_cbc_oracle() {
+        let cbc_oracle = |plaintext: &[u8]| {
+            plaintext
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| byte ^ (i as u8))
+                .collect::<Vec<u8>>()
+        };
+        assert_eq!(detect_ecb_or_cbc(cbc_oracle, 16), Mode::Cbc);
+    }
+}
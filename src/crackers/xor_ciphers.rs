@@ -5,6 +5,7 @@ use crate::scorers::hamming_distance::hamming_distance;
 const MIN_KEYSIZE: usize = 2; // The smallest keysize checked for to crack an XOR cipher.
 const MAX_KEYSIZE: usize = 40; // The largest keysize checked for to crack an XOR cipher.
 const NUM_BLOCKS_AVG_DIST: usize = 10; // The number of blocks to calculate the average Hamming distance.
+const NUM_CANDIDATE_KEYSIZES: usize = 5; // The number of candidate keysizes tried when cracking a repeating-key XOR cipher.
 
 #[derive(Debug)]
 pub struct EmptyArrayError;
@@ -12,14 +13,22 @@ pub struct EmptyArrayError;
 /// Returns the key that was used to encrypt a ciphertext under a single-byte XOR cipher.
 pub fn find_key_single_byte_xor_cipher(ciphertext: &[u8]) -> u8 {
     (0u8..255)
-        .max_by(|x, y| {
-            // We XOR both potential keys against the ciphertext, and choose the one that generates
-            // the most "english-like" plaintext.
+        .min_by(|x, y| {
+            // We XOR both potential keys against the ciphertext, and choose the one whose
+            // letter distribution is the closest fit to English under a chi-squared test.
             let xor_one = xor(ciphertext, &x);
             let xor_two = xor(ciphertext, &y);
-            english_score(xor_one.as_slice()).total_cmp(&english_score(xor_two.as_slice()))
+            chi_squared_english_score(xor_one.as_slice()).total_cmp(&chi_squared_english_score(xor_two.as_slice()))
         })
-        .expect("we know a maximum will be found")
+        .expect("we know a minimum will be found")
+}
+
+/// Returns the key that was used to encrypt a ciphertext under a single-byte XOR cipher, together
+/// with its chi-squared confidence score. Lower scores are more confident.
+pub fn crack_single_byte_xor_with_confidence(ciphertext: &[u8]) -> (u8, f64) {
+    let key = find_key_single_byte_xor_cipher(ciphertext);
+    let confidence = chi_squared_english_score(xor(ciphertext, &key).as_slice());
+    (key, confidence)
 }
 
 /// Returns the plaintext encoded using a single-byte XOR cipher among a list of possible
@@ -29,14 +38,13 @@ pub fn detect_and_crack_single_byte_xor_cipher(possible_ciphertexts: &[&[u8]]) -
         return Err(EmptyArrayError);
     }
 
-    let plaintext = possible_ciphertexts
+    let (ciphertext, (key, _)) = possible_ciphertexts
         .iter()
-        .map(|x| xor(x, &find_key_single_byte_xor_cipher(x)))
-        .max_by(|x, y| english_score(x).total_cmp(&english_score(y)))
-        .expect("we know a maximum will be found")
-        .to_vec();
+        .map(|ciphertext| (ciphertext, crack_single_byte_xor_with_confidence(ciphertext)))
+        .min_by(|(_, (_, x)), (_, (_, y))| x.total_cmp(y))
+        .expect("we know a minimum will be found");
 
-    Ok(plaintext)
+    Ok(xor(ciphertext, &key))
 }
 
 /// Finds the key size (of between 2 and 40 bytes) used to encrypt a repeating XOR cipher.
@@ -48,6 +56,51 @@ pub fn find_key_size_repeating_xor_cipher(ciphertext: &[u8]) -> usize {
         .expect("we know a minimum will be found")
 }
 
+/// Returns the `n` keysizes (of between 2 and 40 bytes) with the smallest average Hamming
+/// distances, sorted ascending by distance. The true keysize isn't always the smallest, so
+/// callers should try each candidate in turn.
+pub fn find_candidate_key_sizes(ciphertext: &[u8], n: usize) -> Vec<usize> {
+    let mut candidate_keysizes: Vec<usize> = (MIN_KEYSIZE..MAX_KEYSIZE+1).collect();
+    candidate_keysizes.sort_by(|x, y| average_hamming_distance(ciphertext, x)
+        .total_cmp(&average_hamming_distance(ciphertext, y)));
+    candidate_keysizes.truncate(n);
+    candidate_keysizes
+}
+
+/// Cracks a repeating-key XOR cipher, returning the recovered key and the decrypted plaintext.
+pub fn crack_repeating_key_xor(ciphertext: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    find_candidate_key_sizes(ciphertext, NUM_CANDIDATE_KEYSIZES)
+        .iter()
+        .map(|keysize| crack_repeating_key_xor_with_keysize(ciphertext, *keysize))
+        .max_by(|x, y| english_score(&x.1).total_cmp(&english_score(&y.1)))
+        .expect("we know a maximum will be found")
+}
+
+/// Cracks a repeating-key XOR cipher assuming the given keysize, returning the recovered key and
+/// the decrypted plaintext.
+fn crack_repeating_key_xor_with_keysize(ciphertext: &[u8], keysize: usize) -> (Vec<u8>, Vec<u8>) {
+    let chunks: Vec<&[u8]> = ciphertext.chunks_exact(keysize).collect();
+
+    let key: Vec<u8> = (0..keysize)
+        .map(|i| {
+            let ith_chunk_entries = chunks
+                .iter()
+                .map(|chunk| chunk[i])
+                .collect::<Vec<u8>>();
+
+            find_key_single_byte_xor_cipher(&ith_chunk_entries)
+        })
+        .collect();
+
+    let plaintext = ciphertext
+        .iter()
+        .zip(key.iter().cycle())
+        .map(|(byte, key_byte)| byte ^ key_byte)
+        .collect();
+
+    (key, plaintext)
+}
+
 /// Returns the average Hamming distance across consecutive blocks of the provided text.
 fn average_hamming_distance(text: &[u8], block_size: &usize) -> f64 {
     let total_hamming_distance: usize = (0..NUM_BLOCKS_AVG_DIST)
@@ -105,34 +158,19 @@ mod tests {
     // Solution to Cryptopals set 01 challenge 06.
     #[test]
     fn can_detect_and_crack_repeating_key_xor_cipher() {
-        // todo - joel - clean up the empty expects
         let filename = "./src/crackers/6.txt";
         let file = File::open(filename).expect("could not open file");
         let ciphertext_base64 = BufReader::new(file)
             .lines()
-            .map(|x| x.expect(""))
+            .map(|x| x.expect("could not read line"))
             .collect::<Vec<String>>()
             .join("");
 
-        let ciphertext = base64::decode(ciphertext_base64).expect("");
-
-        let keysize = find_key_size_repeating_xor_cipher(&ciphertext);
-
-        let chunks: Vec<&[u8]> = ciphertext.chunks_exact(keysize).collect();
-
-        let key: Vec<u8> = (0..keysize)
-            .map(|i| {
-                let ith_chunk_entries = chunks
-                    .iter()
-                    .map(|chunk| chunk[i])
-                    .collect::<Vec<u8>>();
-
-                find_key_single_byte_xor_cipher(&ith_chunk_entries)
-            })
-            .collect();
+        let ciphertext = base64::decode(ciphertext_base64).expect("could not convert base64 to bytes");
 
-        println!("{}", from_utf8(&key).expect(""))
+        let (key, plaintext) = crack_repeating_key_xor(&ciphertext);
 
-        // TODO - Finish writing this test.
+        assert_eq!(from_utf8(&key).expect("key was not valid utf8"), "Terminator X: Bring the noise");
+        assert!(from_utf8(&plaintext).expect("plaintext was not valid utf8").starts_with("I'm back and I'm ringin' the bell"));
     }
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+// Standard English letter frequencies (a-z), used to judge how closely a candidate plaintext's
+// letter distribution matches ordinary English prose.
+const LETTER_FREQUENCIES: [f64; 26] = [
+    0.0804, 0.0148, 0.0334, 0.0382, 0.1249, 0.0240, 0.0187, 0.0505, 0.0757, 0.0016, 0.0054, 0.0407,
+    0.0251, 0.0723, 0.0764, 0.0214, 0.0012, 0.0628, 0.0651, 0.0928, 0.0273, 0.0100, 0.0192, 0.0019,
+    0.0173, 0.0009,
+];
+
+// A penalty applied per byte of a candidate plaintext that falls outside printable ASCII, so that
+// keys producing garbage are never favoured over keys producing English text.
+const NON_PRINTABLE_PENALTY: f64 = 1_000.0;
+
+/// Scores how "English-like" a candidate plaintext is, by summing the standard frequency of each
+/// letter it contains and rewarding spaces. Higher scores are more English-like.
+pub fn english_score(text: &[u8]) -> f64 {
+    text.iter()
+        .map(|&byte| {
+            if byte == b' ' {
+                0.15
+            } else if byte.is_ascii_alphabetic() {
+                let index = byte.to_ascii_lowercase() - b'a';
+                LETTER_FREQUENCIES[index as usize]
+            } else if byte.is_ascii_graphic() {
+                0.0
+            } else {
+                -1.0
+            }
+        })
+        .sum()
+}
+
+/// Scores how closely a candidate plaintext resembles English, using a chi-squared goodness-of-fit
+/// test against the standard English letter frequencies. Lower scores indicate a closer match, so
+/// callers should `min_by` over this score. Text containing no letters scores `f64::INFINITY`.
+pub fn chi_squared_english_score(text: &[u8]) -> f64 {
+    let mut letter_counts = [0usize; 26];
+    let mut total_letters = 0usize;
+    let mut penalty = 0.0;
+
+    for &byte in text {
+        if !byte.is_ascii() || (byte.is_ascii_control() && byte != b' ') {
+            penalty += NON_PRINTABLE_PENALTY;
+            continue;
+        }
+
+        if byte.is_ascii_alphabetic() {
+            let index = byte.to_ascii_lowercase() - b'a';
+            letter_counts[index as usize] += 1;
+            total_letters += 1;
+        }
+    }
+
+    // With no letters every expected count is zero, and 0/0 would give a NaN score.
+    if total_letters == 0 {
+        return f64::INFINITY;
+    }
+
+    let chi_squared: f64 = letter_counts
+        .iter()
+        .zip(LETTER_FREQUENCIES.iter())
+        .map(|(&observed, &frequency)| {
+            let expected = total_letters as f64 * frequency;
+            (observed as f64 - expected).powi(2) / expected
+        })
+        .sum();
+
+    chi_squared + penalty
+}